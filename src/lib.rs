@@ -1,11 +1,13 @@
 use near_sdk::json_types::{U128, ValidAccountId};
-use near_sdk::{near_bindgen, AccountId, env, Balance, log};
-use near_sdk::collections::{LazyOption};
+use near_sdk::{near_bindgen, AccountId, env, Balance, log, Promise};
+use near_sdk::collections::{LazyOption, UnorderedSet};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 
 use near_contract_standards::fungible_token::FungibleToken;
+use near_contract_standards::fungible_token::events::{FtMint, FtBurn};
 use near_contract_standards::storage_management::{StorageManagement, StorageBalance, StorageBalanceBounds};
 use near_contract_standards::fungible_token::core::{FungibleTokenCore};
+use near_contract_standards::fungible_token::resolver::FungibleTokenResolver;
 use near_contract_standards::fungible_token::metadata::{ FungibleTokenMetadata, FT_METADATA_SPEC, FungibleTokenMetadataProvider};
 
 #[near_bindgen]
@@ -13,6 +15,36 @@ use near_contract_standards::fungible_token::metadata::{ FungibleTokenMetadata,
 pub struct Contract {
     token : FungibleToken,
     metadata: LazyOption<FungibleTokenMetadata>,
+    // When `true` the contract behaves like wrapped NEAR: total supply tracks the
+    // NEAR balance held by the contract 1:1 via `near_deposit`/`near_withdraw`.
+    wrapped: bool,
+    owner_id: AccountId,
+    paused: bool,
+    admins: UnorderedSet<AccountId>,
+}
+
+/// Mirrors the pre-upgrade `Contract` layout (`token`/`metadata` only, as
+/// deployed before the owner/wrapped/pause/admin fields were added) so
+/// `migrate` can read old state and backfill the new fields with defaults.
+/// Bump this struct in lockstep with `Contract` the next time its layout
+/// changes, so it always reflects the *previously* deployed shape.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldContract {
+    token: FungibleToken,
+    metadata: LazyOption<FungibleTokenMetadata>,
+}
+
+const BASE_GAS_BUFFER: u64 = 5_000_000_000_000;
+const BATCH_RESOLVE_GAS: u64 = 5_000_000_000_000;
+
+/// Decodes a hex string (no `0x` prefix) into bytes, panicking on malformed
+/// input. Used to validate `payment_reference` in `ft_transfer_with_reference`.
+fn decode_hex(s: &str) -> Vec<u8> {
+    assert_eq!(s.len() % 2, 0, "payment_reference must have an even number of hex digits");
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).expect("payment_reference must be valid hex"))
+        .collect()
 }
 
 
@@ -46,22 +78,359 @@ impl Contract {
         let mut this = Self {
             token: FungibleToken::new(b"a".to_vec()),
             metadata: LazyOption::new(b"m".to_vec(), Some(&metadata)),
+            wrapped: false,
+            owner_id: owner_id.clone(),
+            paused: false,
+            admins: UnorderedSet::new(b"d".to_vec()),
         };
         this.token.internal_register_account(&owner_id);
         this.token.internal_deposit(&owner_id, total_supply.into());
-        // near_contract_standards::fungible_token
-        // near_contract_standards::fungible_token::events::FtMint{
-        //     owner_id: &owner_id,
-        //     amount: &total_supply,
-        //     memo: Some("Initial tokens supply is minted"),
-        // }.emit();
+        FtMint {
+            owner_id: &owner_id,
+            amount: &total_supply,
+            memo: Some("Initial tokens supply is minted"),
+        }
+        .emit();
         this
     }
+
+    /// Initializes the contract as wrapped NEAR: no initial supply is minted,
+    /// instead accounts mint/burn 1:1 against attached NEAR through
+    /// `near_deposit`/`near_withdraw`. Requires 24 decimals so FT amounts are
+    /// denominated in yoctoNEAR, matching the w-near contract.
+    #[init]
+    pub fn new_wrapped(owner_id: AccountId, metadata: FungibleTokenMetadata) -> Self {
+        assert!(!env::state_exists(), "Contract already initialized");
+        metadata.assert_valid();
+        assert_eq!(metadata.decimals, 24, "Wrapped mode requires 24 decimals so amounts equal yoctoNEAR");
+        Self {
+            token: FungibleToken::new(b"a".to_vec()),
+            metadata: LazyOption::new(b"m".to_vec(), Some(&metadata)),
+            wrapped: true,
+            owner_id,
+            paused: false,
+            admins: UnorderedSet::new(b"d".to_vec()),
+        }
+    }
+
+    /// Mints FT to the caller equal to the attached deposit, less the storage
+    /// cost when the caller is not yet registered. Only available when the
+    /// contract was initialized with `new_wrapped`.
+    #[payable]
+    pub fn near_deposit(&mut self) {
+        assert!(self.wrapped, "Contract is not configured for wrapped NEAR");
+        let amount = env::attached_deposit();
+        let account_id = env::predecessor_account_id();
+        let valid_account_id = ValidAccountId::try_from(account_id.clone()).unwrap();
+        let mintable = if self.token.storage_balance_of(valid_account_id).is_some() {
+            amount
+        } else {
+            // Registration costs real storage; charge it out of the attached
+            // deposit instead of minting it for free, or the contract's NEAR
+            // reserve would stop tracking total supply 1:1.
+            let bounds = self.token.storage_balance_bounds();
+            assert!(
+                amount >= bounds.min.0,
+                "Attached deposit must cover the account's storage cost ({} yoctoNEAR) before minting",
+                bounds.min.0
+            );
+            self.token.internal_register_account(&account_id);
+            amount - bounds.min.0
+        };
+        assert!(mintable > 0, "Requires a positive attached deposit after storage costs");
+        self.token.internal_deposit(&account_id, mintable);
+    }
+
+    /// Burns `amount` FT from the caller and sends back the same amount of
+    /// yoctoNEAR. Only available when the contract was initialized with
+    /// `new_wrapped`.
+    #[payable]
+    pub fn near_withdraw(&mut self, amount: U128) -> Promise {
+        near_sdk::assert_one_yocto();
+        assert!(self.wrapped, "Contract is not configured for wrapped NEAR");
+        let account_id = env::predecessor_account_id();
+        self.token.internal_withdraw(&account_id, amount.into());
+        Promise::new(account_id).transfer(amount.into())
+    }
+
+    /// Mints `amount` new FT to `account_id`, registering it first if needed.
+    /// Restricted to the contract owner.
+    pub fn ft_mint(&mut self, account_id: ValidAccountId, amount: U128, memo: Option<String>) {
+        self.assert_owner();
+        assert!(
+            !self.wrapped,
+            "Wrapped NEAR supply is minted only through near_deposit"
+        );
+        let account: AccountId = account_id.clone().into();
+        if self.token.storage_balance_of(account_id).is_none() {
+            self.token.internal_register_account(&account);
+        }
+        self.token.internal_deposit(&account, amount.into());
+        FtMint {
+            owner_id: &account,
+            amount: &amount,
+            memo: memo.as_deref(),
+        }
+        .emit();
+    }
+
+    /// Burns `amount` FT from the owner's own account. Restricted to the
+    /// contract owner.
+    pub fn ft_burn(&mut self, amount: U128, memo: Option<String>) {
+        self.assert_owner();
+        assert!(
+            !self.wrapped,
+            "Wrapped NEAR supply is burned only through near_withdraw"
+        );
+        let account_id = env::predecessor_account_id();
+        self.token.internal_withdraw(&account_id, amount.into());
+        FtBurn {
+            owner_id: &account_id,
+            amount: &amount,
+            memo: memo.as_deref(),
+        }
+        .emit();
+    }
+
+    fn assert_owner(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "Only the contract owner can call this method"
+        );
+    }
+
+    fn assert_admin(&self) {
+        let predecessor = env::predecessor_account_id();
+        assert!(
+            predecessor == self.owner_id || self.admins.contains(&predecessor),
+            "Only the owner or an admin can call this method"
+        );
+    }
+
+    fn assert_not_paused(&self) {
+        assert!(!self.paused, "Transfers are paused");
+    }
+
+    /// Grants `account_id` admin rights to pause/unpause transfers.
+    /// Restricted to the contract owner.
+    pub fn pa_add_admin(&mut self, account_id: ValidAccountId) {
+        self.assert_owner();
+        self.admins.insert(&account_id.into());
+    }
+
+    /// Revokes `account_id`'s admin rights. Restricted to the contract owner.
+    pub fn pa_remove_admin(&mut self, account_id: ValidAccountId) {
+        self.assert_owner();
+        self.admins.remove(&account_id.into());
+    }
+
+    /// Freezes `ft_transfer`/`ft_transfer_call`. Restricted to the owner or
+    /// an admin. View methods keep working while paused.
+    pub fn pa_pause(&mut self) {
+        self.assert_admin();
+        self.paused = true;
+        log!("Transfers paused by @{}", env::predecessor_account_id());
+    }
+
+    /// Unfreezes transfers. Restricted to the owner or an admin.
+    pub fn pa_unpause(&mut self) {
+        self.assert_admin();
+        self.paused = false;
+        log!("Transfers unpaused by @{}", env::predecessor_account_id());
+    }
+
+    /// Whether transfers are currently paused.
+    pub fn ft_is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Transfers `amounts[i]` to `receiver_ids[i]` for each index, requiring
+    /// a single attached yoctoNEAR for the whole batch rather than one per
+    /// leg. Lets airdrop/payroll callers move many balances in one transaction.
+    #[payable]
+    pub fn ft_batch_transfer(
+        &mut self,
+        receiver_ids: Vec<ValidAccountId>,
+        amounts: Vec<U128>,
+        memo: Option<String>,
+    ) {
+        near_sdk::assert_one_yocto();
+        self.assert_not_paused();
+        assert!(!receiver_ids.is_empty(), "receiver_ids must not be empty");
+        assert_eq!(
+            receiver_ids.len(),
+            amounts.len(),
+            "receiver_ids and amounts must have the same length"
+        );
+        for (receiver_id, amount) in receiver_ids.into_iter().zip(amounts.into_iter()) {
+            self.token.ft_transfer(receiver_id, amount, memo.clone());
+        }
+    }
+
+    /// `ft_transfer_call` variant of `ft_batch_transfer`. Each leg's
+    /// cross-contract call is joined with `Promise::and` and only resolved
+    /// once every receiver has responded, via `ft_resolve_batch_transfer`.
+    #[payable]
+    pub fn ft_batch_transfer_call(
+        &mut self,
+        receiver_ids: Vec<ValidAccountId>,
+        amounts: Vec<U128>,
+        memo: Option<String>,
+        msgs: Vec<String>,
+    ) -> near_sdk::PromiseOrValue<Vec<U128>> {
+        near_sdk::assert_one_yocto();
+        self.assert_not_paused();
+        assert!(!receiver_ids.is_empty(), "receiver_ids must not be empty");
+        assert_eq!(
+            receiver_ids.len(),
+            amounts.len(),
+            "receiver_ids and amounts must have the same length"
+        );
+        assert_eq!(
+            receiver_ids.len(),
+            msgs.len(),
+            "receiver_ids and msgs must have the same length"
+        );
+
+        let count = receiver_ids.len() as u64;
+        let mut joined: Option<Promise> = None;
+        for ((receiver_id, amount), msg) in receiver_ids
+            .into_iter()
+            .zip(amounts.into_iter())
+            .zip(msgs.into_iter())
+        {
+            let leg = match self.token.ft_transfer_call(receiver_id, amount, memo.clone(), msg) {
+                near_sdk::PromiseOrValue::Promise(promise) => promise,
+                // The standard impl only takes this path for a same-shard
+                // shortcut; still feed the resolver a promise for this leg.
+                near_sdk::PromiseOrValue::Value(_) => Promise::new(env::current_account_id()),
+            };
+            joined = Some(match joined {
+                Some(acc) => acc.and(leg),
+                None => leg,
+            });
+        }
+
+        near_sdk::PromiseOrValue::Promise(joined.unwrap().then(Promise::new(env::current_account_id()).function_call(
+            "ft_resolve_batch_transfer".to_string(),
+            near_sdk::serde_json::to_vec(&near_sdk::serde_json::json!({ "count": count })).unwrap(),
+            0,
+            BATCH_RESOLVE_GAS,
+        )))
+    }
+
+    /// Callback for `ft_batch_transfer_call`: reads each leg's promise result
+    /// in order and reports the amount each leg used, as returned by
+    /// `ft_resolve_transfer` (0 if the leg itself failed).
+    #[private]
+    pub fn ft_resolve_batch_transfer(&mut self, count: u64) -> Vec<U128> {
+        (0..count)
+            .map(|i| match env::promise_result(i as usize) {
+                near_sdk::PromiseResult::Successful(value) => {
+                    near_sdk::serde_json::from_slice::<U128>(&value).unwrap_or(U128(0))
+                }
+                _ => U128(0),
+            })
+            .collect()
+    }
+
+    /// Transfers `amount` to `receiver_id` plus `fee_amount` to `fee_address`,
+    /// then logs a structured event carrying `payment_reference` so off-chain
+    /// systems can reconcile the transfer to an invoice without a separate
+    /// payment-proxy contract.
+    #[payable]
+    pub fn ft_transfer_with_reference(
+        &mut self,
+        receiver_id: ValidAccountId,
+        amount: U128,
+        payment_reference: String,
+        fee_amount: U128,
+        fee_address: ValidAccountId,
+        memo: Option<String>,
+    ) {
+        self.assert_not_paused();
+        let reference_bytes = decode_hex(&payment_reference);
+        assert_eq!(
+            reference_bytes.len(),
+            8,
+            "payment_reference must decode to exactly 8 bytes"
+        );
+
+        self.token.ft_transfer(receiver_id.clone(), amount, memo.clone());
+        if fee_amount.0 > 0 {
+            self.token.ft_transfer(fee_address.clone(), fee_amount, memo);
+        }
+
+        log!(
+            "EVENT_JSON:{}",
+            near_sdk::serde_json::json!({
+                "standard": "ft-payment",
+                "version": "1.0.0",
+                "event": "transfer_with_reference",
+                "data": [{
+                    "receiver_id": AccountId::from(receiver_id),
+                    "amount": amount,
+                    "payment_reference": payment_reference,
+                    "fee_amount": fee_amount,
+                    "fee_address": AccountId::from(fee_address),
+                }]
+            })
+        );
+    }
+
+    /// Redeploys the contract with the WASM passed as raw input, then calls
+    /// `migrate` on itself with the remaining gas. Restricted to the owner.
+    pub fn upgrade(&self) {
+        self.assert_owner();
+        let code = env::input().expect("Error: No input").to_vec();
+        let migrate_args = near_sdk::serde_json::to_vec(&near_sdk::serde_json::json!({
+            "owner_id": self.owner_id,
+        }))
+        .unwrap();
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call(
+                "migrate".to_string(),
+                migrate_args,
+                0,
+                env::prepaid_gas() - env::used_gas() - BASE_GAS_BUFFER,
+            );
+    }
+
+    /// Reads the previously deployed `OldContract` layout and backfills the
+    /// fields it didn't have with defaults. `owner_id` must be supplied since
+    /// the pre-upgrade layout didn't track one. Called by `upgrade` right
+    /// after redeploy.
+    #[init(ignore_state)]
+    pub fn migrate(owner_id: AccountId) -> Self {
+        assert_eq!(
+            env::predecessor_account_id(),
+            env::current_account_id(),
+            "migrate must be called by the contract itself"
+        );
+        let old: OldContract = env::state_read().expect("Error: contract state is not initialized");
+        Self {
+            token: old.token,
+            metadata: old.metadata,
+            wrapped: false,
+            owner_id,
+            paused: false,
+            admins: UnorderedSet::new(b"d".to_vec()),
+        }
+    }
+
     fn on_account_closed(&mut self, account_id: AccountId, balance:Balance) {
         log!("Closed @{} with {}", account_id, balance);
     }
     fn on_tokens_burned(&mut self, account_id: AccountId, amount: Balance) {
         log!("Account @{} burned {}", account_id, amount);
+        FtBurn {
+            owner_id: &account_id,
+            amount: &U128(amount),
+            memo: Some("storage_unregister burn"),
+        }
+        .emit();
     }
 }
 
@@ -69,11 +438,13 @@ impl Contract {
 #[near_bindgen]
 impl FungibleTokenCore for Contract {
     fn ft_transfer(&mut self, receiver_id: near_sdk::json_types::ValidAccountId, amount: U128, memo: Option<String>) {
+        self.assert_not_paused();
         self.token.ft_transfer(receiver_id, amount, memo)
     }
 
     #[payable]
     fn ft_transfer_call(&mut self, receiver_id: near_sdk::json_types::ValidAccountId, amount: U128, memo: Option<String>, msg: String) -> near_sdk::PromiseOrValue<U128> {
+        self.assert_not_paused();
         self.token.ft_transfer_call(receiver_id, amount, memo, msg)
     }
 
@@ -87,6 +458,24 @@ impl FungibleTokenCore for Contract {
 }
 
 
+#[near_bindgen]
+impl FungibleTokenResolver for Contract {
+    #[private]
+    fn ft_resolve_transfer(
+        &mut self,
+        sender_id: near_sdk::json_types::ValidAccountId,
+        receiver_id: near_sdk::json_types::ValidAccountId,
+        amount: U128,
+    ) -> U128 {
+        let (used_amount, burned_amount) = self.token.internal_ft_resolve_transfer(&sender_id, receiver_id, amount);
+        if burned_amount > 0 {
+            self.on_tokens_burned(sender_id.into(), burned_amount);
+        }
+        used_amount.into()
+    }
+}
+
+
 #[near_bindgen]
 impl StorageManagement for Contract {
     #[payable]
@@ -168,5 +557,281 @@ mod tests {
         assert_eq!(contract.ft_balance_of(accounts(1)), TOTAL_SUPPLY);
     }
 
+    fn wrapped_metadata() -> FungibleTokenMetadata {
+        FungibleTokenMetadata {
+            spec: FT_METADATA_SPEC.to_string(),
+            name: "Wrapped NEAR".to_string(),
+            symbol: "wNEAR".to_string(),
+            icon: None,
+            reference: None,
+            reference_hash: None,
+            decimals: 24,
+        }
+    }
+
+    #[test]
+    fn test_near_deposit_and_withdraw() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = Contract::new_wrapped(accounts(0).into(), wrapped_metadata());
+        let bounds = contract.storage_balance_bounds();
+
+        let deposit = bounds.min.0 + 1_000_000_000_000_000_000_000_000;
+        testing_env!(context.attached_deposit(deposit).build());
+        contract.near_deposit();
+
+        testing_env!(context.is_view(true).attached_deposit(0).build());
+        assert_eq!(contract.ft_balance_of(accounts(1)).0, deposit - bounds.min.0);
+
+        testing_env!(context.is_view(false).attached_deposit(1).build());
+        let minted = contract.ft_balance_of(accounts(1)).0;
+        contract.near_withdraw(U128(minted));
+
+        testing_env!(context.is_view(true).attached_deposit(0).build());
+        assert_eq!(contract.ft_balance_of(accounts(1)).0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attached deposit must cover the account's storage cost")]
+    fn test_near_deposit_requires_storage_cost() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = Contract::new_wrapped(accounts(0).into(), wrapped_metadata());
+        testing_env!(context.attached_deposit(1).build());
+        contract.near_deposit();
+    }
+
+    #[test]
+    fn test_ft_mint_and_burn() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_meta(accounts(1).into(), TOTAL_SUPPLY.into());
+
+        contract.ft_mint(accounts(2), U128(500), None);
+        testing_env!(context.is_view(true).build());
+        assert_eq!(contract.ft_balance_of(accounts(2)).0, 500);
+
+        testing_env!(context.is_view(false).build());
+        contract.ft_burn(U128(100), None);
+        testing_env!(context.is_view(true).build());
+        assert_eq!(contract.ft_balance_of(accounts(1)).0, TOTAL_SUPPLY - 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the contract owner can call this method")]
+    fn test_ft_mint_requires_owner() {
+        let mut context = get_context(accounts(2));
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_meta(accounts(1).into(), TOTAL_SUPPLY.into());
+        contract.ft_mint(accounts(2), U128(500), None);
+    }
+
+    #[test]
+    fn test_ft_batch_transfer() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_meta(accounts(1).into(), TOTAL_SUPPLY.into());
+        let bounds = contract.storage_balance_bounds();
+
+        testing_env!(context.attached_deposit(bounds.min.0).build());
+        contract.storage_deposit(Some(accounts(2)), Some(true));
+        testing_env!(context.attached_deposit(bounds.min.0).build());
+        contract.storage_deposit(Some(accounts(3)), Some(true));
+
+        testing_env!(context.attached_deposit(1).build());
+        contract.ft_batch_transfer(vec![accounts(2), accounts(3)], vec![U128(100), U128(200)], None);
+
+        testing_env!(context.is_view(true).attached_deposit(0).build());
+        assert_eq!(contract.ft_balance_of(accounts(2)).0, 100);
+        assert_eq!(contract.ft_balance_of(accounts(3)).0, 200);
+        assert_eq!(contract.ft_balance_of(accounts(1)).0, TOTAL_SUPPLY - 300);
+    }
+
+    #[test]
+    #[should_panic(expected = "receiver_ids and amounts must have the same length")]
+    fn test_ft_batch_transfer_requires_matching_lengths() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_meta(accounts(1).into(), TOTAL_SUPPLY.into());
+        testing_env!(context.attached_deposit(1).build());
+        contract.ft_batch_transfer(vec![accounts(2)], vec![U128(1), U128(2)], None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Transfers are paused")]
+    fn test_pause_blocks_transfers() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_meta(accounts(1).into(), TOTAL_SUPPLY.into());
+        let bounds = contract.storage_balance_bounds();
+        testing_env!(context.attached_deposit(bounds.min.0).build());
+        contract.storage_deposit(Some(accounts(2)), Some(true));
+
+        testing_env!(context.attached_deposit(0).build());
+        contract.pa_pause();
+        assert!(contract.ft_is_paused());
+
+        testing_env!(context.attached_deposit(1).build());
+        contract.ft_transfer(accounts(2), U128(10), None);
+    }
+
+    #[test]
+    fn test_pa_add_admin_allows_pausing() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_meta(accounts(1).into(), TOTAL_SUPPLY.into());
+        contract.pa_add_admin(accounts(2));
 
+        testing_env!(get_context(accounts(2)).build());
+        contract.pa_pause();
+        assert!(contract.ft_is_paused());
+        contract.pa_unpause();
+        assert!(!contract.ft_is_paused());
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the owner or an admin can call this method")]
+    fn test_pa_pause_requires_admin() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_meta(accounts(1).into(), TOTAL_SUPPLY.into());
+
+        testing_env!(get_context(accounts(2)).build());
+        contract.pa_pause();
+    }
+
+    #[test]
+    fn test_migrate_backfills_new_fields() {
+        // `migrate` only accepts a call from the contract itself.
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let owner: AccountId = accounts(1).into();
+        let mut token = FungibleToken::new(b"a".to_vec());
+        token.internal_register_account(&owner);
+        token.internal_deposit(&owner, TOTAL_SUPPLY);
+        let old = OldContract {
+            token,
+            metadata: LazyOption::new(
+                b"m".to_vec(),
+                Some(&FungibleTokenMetadata {
+                    spec: FT_METADATA_SPEC.to_string(),
+                    name: "D fungible token".to_string(),
+                    symbol: "D".to_string(),
+                    icon: None,
+                    reference: None,
+                    reference_hash: None,
+                    decimals: 24,
+                }),
+            ),
+        };
+        env::state_write(&old);
+
+        let contract = Contract::migrate(owner.clone());
+        assert_eq!(contract.ft_total_supply().0, TOTAL_SUPPLY);
+        assert!(!contract.ft_is_paused());
+        assert_eq!(contract.owner_id, owner);
+    }
+
+    #[test]
+    fn test_ft_transfer_with_reference() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_meta(accounts(1).into(), TOTAL_SUPPLY.into());
+        let bounds = contract.storage_balance_bounds();
+        testing_env!(context.attached_deposit(bounds.min.0).build());
+        contract.storage_deposit(Some(accounts(2)), Some(true));
+        testing_env!(context.attached_deposit(bounds.min.0).build());
+        contract.storage_deposit(Some(accounts(3)), Some(true));
+
+        testing_env!(context.attached_deposit(1).build());
+        contract.ft_transfer_with_reference(
+            accounts(2),
+            U128(1_000),
+            "0001020304050607".to_string(),
+            U128(10),
+            accounts(3),
+            None,
+        );
+
+        testing_env!(context.is_view(true).attached_deposit(0).build());
+        assert_eq!(contract.ft_balance_of(accounts(2)).0, 1_000);
+        assert_eq!(contract.ft_balance_of(accounts(3)).0, 10);
+        assert_eq!(contract.ft_balance_of(accounts(1)).0, TOTAL_SUPPLY - 1_010);
+    }
+
+    #[test]
+    #[should_panic(expected = "payment_reference must decode to exactly 8 bytes")]
+    fn test_ft_transfer_with_reference_requires_8_bytes() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_meta(accounts(1).into(), TOTAL_SUPPLY.into());
+
+        testing_env!(context.attached_deposit(1).build());
+        contract.ft_transfer_with_reference(
+            accounts(2),
+            U128(1_000),
+            "00010203".to_string(),
+            U128(0),
+            accounts(2),
+            None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "receiver_ids and amounts must have the same length")]
+    fn test_ft_batch_transfer_call_requires_matching_amounts() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_meta(accounts(1).into(), TOTAL_SUPPLY.into());
+        testing_env!(context.attached_deposit(1).build());
+        contract.ft_batch_transfer_call(
+            vec![accounts(2)],
+            vec![U128(1), U128(2)],
+            None,
+            vec!["msg".to_string()],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "receiver_ids and msgs must have the same length")]
+    fn test_ft_batch_transfer_call_requires_matching_msgs() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_meta(accounts(1).into(), TOTAL_SUPPLY.into());
+        testing_env!(context.attached_deposit(1).build());
+        contract.ft_batch_transfer_call(
+            vec![accounts(2)],
+            vec![U128(1)],
+            None,
+            vec!["msg".to_string(), "extra".to_string()],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "receiver_ids must not be empty")]
+    fn test_ft_batch_transfer_call_requires_nonempty() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_meta(accounts(1).into(), TOTAL_SUPPLY.into());
+        testing_env!(context.attached_deposit(1).build());
+        contract.ft_batch_transfer_call(vec![], vec![], None, vec![]);
+    }
+
+    #[test]
+    fn test_ft_resolve_batch_transfer_reads_promise_results_in_order() {
+        let context = get_context(accounts(1));
+        testing_env!(
+            context.build(),
+            near_sdk::VMConfig::default(),
+            near_sdk::RuntimeFeesConfig::default(),
+            Default::default(),
+            vec![
+                near_sdk::PromiseResult::Successful(near_sdk::serde_json::to_vec(&U128(30)).unwrap()),
+                near_sdk::PromiseResult::Failed,
+            ]
+        );
+        let mut contract = Contract::new_default_meta(accounts(1).into(), TOTAL_SUPPLY.into());
+        let results = contract.ft_resolve_batch_transfer(2);
+        assert_eq!(results, vec![U128(30), U128(0)]);
+    }
 }
\ No newline at end of file